@@ -1,10 +1,30 @@
-use crate::{ExistingInstall, InstallerConfig};
+use crate::{ExistingInstall, InstallBackup, InstallerConfig};
+use serde::Deserialize;
 use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
+use std::io::Read;
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 use uuid::Uuid;
 
+/// Maps each bundled file's path (relative to the install dir) to its
+/// expected SHA256 digest.
+///
+/// KNOWN LIMITATION: there is no manifest signature here, and no public key
+/// infrastructure in the installer to verify one if there were. This only
+/// catches corrupted or incomplete copies of the bundle — a tampered bundle
+/// could ship a tampered `manifest.json` to match, so this is checksum-only
+/// corruption detection, not the tamper-resistant signed-manifest guarantee
+/// that was originally asked for. Revisit if/when the packaging pipeline can
+/// sign releases and the installer can be given a public key to check
+/// against.
+#[derive(Debug, Deserialize)]
+struct IntegrityManifest {
+    files: HashMap<String, String>,
+}
+
 /// Recursively copy a directory
 fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
     if !dst.exists() {
@@ -28,7 +48,7 @@ fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
 }
 
 /// Get the SignalK configuration directory
-fn get_config_dir() -> PathBuf {
+pub(crate) fn get_config_dir() -> PathBuf {
     dirs::home_dir()
         .map(|h| h.join(".signalk"))
         .unwrap_or_else(|| PathBuf::from(".signalk"))
@@ -76,12 +96,16 @@ pub fn check_existing_install() -> ExistingInstall {
             .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
             .and_then(|json| json.get("version").and_then(|v| v.as_str().map(String::from)));
 
+        log::info!("Found existing installation at {} (version {:?})", config_dir.display(), version);
+
         ExistingInstall {
             found: true,
             config_path: Some(config_dir.to_string_lossy().to_string()),
             version,
         }
     } else {
+        log::info!("No existing installation found at {}", config_dir.display());
+
         ExistingInstall {
             found: false,
             config_path: None,
@@ -90,6 +114,18 @@ pub fn check_existing_install() -> ExistingInstall {
     }
 }
 
+/// Read the previously selected update channel from the installer settings,
+/// defaulting to the stable channel when none has been persisted yet
+pub(crate) fn read_channel() -> String {
+    let installer_settings_path = get_config_dir().join("installer.json");
+
+    fs::read_to_string(&installer_settings_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|json| json.get("channel").and_then(|c| c.as_str().map(String::from)))
+        .unwrap_or_else(|| "stable".to_string())
+}
+
 /// Emit installation progress event
 fn emit_progress(app: &AppHandle, step: &str, status: &str, message: Option<&str>) {
     let _ = app.emit(
@@ -102,19 +138,203 @@ fn emit_progress(app: &AppHandle, step: &str, status: &str, message: Option<&str
     );
 }
 
-/// Run the installation process
-pub async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result<(), String> {
+/// Create a timestamped backup of an existing directory (config or install),
+/// so a failed install/upgrade can be rolled back to a known-good state.
+/// `label` distinguishes the two backup families on disk, e.g. "signalk" for
+/// the config dir producing `.signalk.bak-<timestamp>`
+fn backup_existing_dir(dir: &PathBuf, label: &str) -> Result<Option<PathBuf>, String> {
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    // RFC3339 contains `:`, which NTFS rejects in filenames, so use a
+    // filename-safe format that still sorts chronologically
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let backup_dir = dir
+        .parent()
+        .map(|parent| parent.join(format!(".{}.bak-{}", label, timestamp)))
+        .ok_or("Could not determine backup directory location")?;
+
+    copy_dir_recursive(dir, &backup_dir).map_err(|e| {
+        let message = format!("Failed to back up {}: {}", dir.display(), e);
+        log::error!("{}", message);
+        message
+    })?;
+
+    log::info!("Backed up {} to {}", dir.display(), backup_dir.display());
+
+    Ok(Some(backup_dir))
+}
+
+/// Confirm `backup_path` is actually a backup `backup_existing_dir` created
+/// for `target_dir` — a sibling directory named `.{label}.bak-<timestamp>` —
+/// before anything is allowed to act on it. Without this check a caller of
+/// the `restore_backup` IPC command could point it at an arbitrary path and
+/// have it deleted and overwritten in place of the live config/install dir
+fn validate_backup_path(backup_dir: &PathBuf, target_dir: &PathBuf, label: &str) -> Result<(), String> {
+    let expected_parent = target_dir
+        .parent()
+        .ok_or("Could not determine backup directory location")?;
+
+    let actual_parent = backup_dir
+        .parent()
+        .ok_or_else(|| format!("Invalid backup path: {}", backup_dir.display()))?;
+
+    if actual_parent != expected_parent {
+        return Err(format!("Refusing to restore from untrusted path: {}", backup_dir.display()));
+    }
+
+    let prefix = format!(".{}.bak-", label);
+    let matches_name = backup_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| name.starts_with(&prefix));
+
+    if !matches_name {
+        return Err(format!("Refusing to restore from untrusted path: {}", backup_dir.display()));
+    }
+
+    Ok(())
+}
+
+/// Replace `target_dir` with the contents of a previously taken backup
+fn restore_dir_backup(backup_path: &str, target_dir: &PathBuf, label: &str) -> Result<(), String> {
+    let backup_dir = PathBuf::from(backup_path);
+    if !backup_dir.exists() {
+        return Err(format!("Backup not found: {}", backup_path));
+    }
+
+    validate_backup_path(&backup_dir, target_dir, label)?;
+
+    if target_dir.exists() {
+        fs::remove_dir_all(target_dir)
+            .map_err(|e| format!("Failed to remove {}: {}", target_dir.display(), e))?;
+    }
+
+    copy_dir_recursive(&backup_dir, target_dir)
+        .map_err(|e| format!("Failed to restore backup into {}: {}", target_dir.display(), e))
+}
+
+/// Restore previously taken config and/or install backups, replacing
+/// whatever is currently in those directories. Both restores are attempted
+/// even if one fails, so a failure on one side doesn't leave the other
+/// silently un-restored
+pub fn restore_backup(config_backup_path: Option<String>, install_backup_path: Option<String>) -> Result<(), String> {
+    let mut errors = Vec::new();
+
+    if let Some(path) = config_backup_path {
+        match restore_dir_backup(&path, &get_config_dir(), "signalk") {
+            Ok(()) => log::info!("Restored configuration from backup {}", path),
+            Err(e) => {
+                log::error!("Failed to restore configuration backup {}: {}", path, e);
+                errors.push(format!("configuration: {}", e));
+            }
+        }
+    }
+
+    if let Some(path) = install_backup_path {
+        match restore_dir_backup(&path, &get_install_dir(), "signalk-install") {
+            Ok(()) => log::info!("Restored installation from backup {}", path),
+            Err(e) => {
+                log::error!("Failed to restore installation backup {}: {}", path, e);
+                errors.push(format!("installation: {}", e));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Run the installation process, backing up any existing config and install
+/// directories first and rolling back to them if installation fails partway
+/// through
+pub async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result<InstallBackup, String> {
     let config_dir = get_config_dir();
     let install_dir = get_install_dir();
 
+    log::info!("Starting installation into {}", install_dir.display());
+
+    let config_backup = backup_existing_dir(&config_dir, "signalk")?;
+    let install_backup = backup_existing_dir(&install_dir, "signalk-install")?;
+
+    match run_installation_steps(&app, &config, &config_dir, &install_dir).await {
+        Ok(service_warning) => {
+            log::info!("Installation completed successfully");
+            Ok(InstallBackup {
+                config_backup_path: config_backup.map(|p| p.to_string_lossy().to_string()),
+                install_backup_path: install_backup.map(|p| p.to_string_lossy().to_string()),
+                service_warning,
+            })
+        }
+        Err(e) => {
+            log::warn!("Installation failed ({}), rolling back", e);
+
+            // Discard whatever the failed install wrote, then restore the
+            // previous config/install directories, if there were any
+            let _ = fs::remove_dir_all(&config_dir);
+            let _ = fs::remove_dir_all(&install_dir);
+
+            let mut rollback_failed = false;
+
+            if let Some(backup_dir) = &config_backup {
+                if let Err(restore_err) = copy_dir_recursive(backup_dir, &config_dir) {
+                    log::error!("Rollback failed to restore configuration backup: {}", restore_err);
+                    rollback_failed = true;
+                }
+            }
+            if let Some(backup_dir) = &install_backup {
+                if let Err(restore_err) = copy_dir_recursive(backup_dir, &install_dir) {
+                    log::error!("Rollback failed to restore installation backup: {}", restore_err);
+                    rollback_failed = true;
+                }
+            }
+
+            if rollback_failed {
+                emit_progress(
+                    &app,
+                    "rollback",
+                    "error",
+                    Some("Installation failed and automatic rollback did not fully succeed; use restore_backup or check the install log"),
+                );
+            } else {
+                emit_progress(
+                    &app,
+                    "rollback",
+                    "completed",
+                    Some("Installation failed; restored previous configuration"),
+                );
+            }
+
+            Err(e)
+        }
+    }
+}
+
+/// The installation steps proper, run after any existing configuration has
+/// already been backed up by `run_installation`. Returns a non-fatal service
+/// warning, if auto-start was requested but writing or activating the service
+/// definition failed — every other failure in these steps is fatal and rolls
+/// the install back,
+/// but a missing/broken service manager shouldn't discard an otherwise-good
+/// installation
+async fn run_installation_steps(
+    app: &AppHandle,
+    config: &InstallerConfig,
+    config_dir: &PathBuf,
+    install_dir: &PathBuf,
+) -> Result<Option<String>, String> {
     // Step 1: Extract files
-    emit_progress(&app, "extract", "in_progress", Some("Preparing installation directory..."));
+    emit_progress(app, "extract", "in_progress", Some("Preparing installation directory..."));
 
-    fs::create_dir_all(&config_dir).map_err(|e| format!("Failed to create config directory: {}", e))?;
-    fs::create_dir_all(&install_dir).map_err(|e| format!("Failed to create install directory: {}", e))?;
+    fs::create_dir_all(config_dir).map_err(|e| format!("Failed to create config directory: {}", e))?;
+    fs::create_dir_all(install_dir).map_err(|e| format!("Failed to create install directory: {}", e))?;
 
     // Extract bundled Node.js and signalk-server from resources
-    emit_progress(&app, "extract", "in_progress", Some("Extracting Node.js and SignalK Server..."));
+    emit_progress(app, "extract", "in_progress", Some("Extracting Node.js and SignalK Server..."));
 
     // Get the resource directory from Tauri
     let resource_path = app.path().resource_dir()
@@ -132,6 +352,7 @@ pub async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result
     if bundled_node.exists() {
         fs::copy(&bundled_node, &target_node)
             .map_err(|e| format!("Failed to copy Node.js: {}", e))?;
+        log::info!("Copied Node.js binary to {}", target_node.display());
 
         // Make executable on Unix
         #[cfg(unix)]
@@ -144,6 +365,8 @@ pub async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result
             fs::set_permissions(&target_node, perms)
                 .map_err(|e| format!("Failed to set node permissions: {}", e))?;
         }
+    } else {
+        log::warn!("No bundled Node.js binary found at {}", bundled_node.display());
     }
 
     // Copy signalk-server directory
@@ -153,12 +376,15 @@ pub async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result
     if bundled_server.exists() {
         copy_dir_recursive(&bundled_server, &target_server)
             .map_err(|e| format!("Failed to copy SignalK Server: {}", e))?;
+        log::info!("Copied SignalK Server to {}", target_server.display());
+    } else {
+        log::warn!("No bundled SignalK Server found at {}", bundled_server.display());
     }
 
-    emit_progress(&app, "extract", "completed", None);
+    emit_progress(app, "extract", "completed", None);
 
     // Step 2: Create configuration
-    emit_progress(&app, "config", "in_progress", Some("Writing configuration files..."));
+    emit_progress(app, "config", "in_progress", Some("Writing configuration files..."));
 
     // Generate UUID if no MMSI provided
     let vessel_uuid = if config.mmsi.is_empty() {
@@ -239,32 +465,156 @@ pub async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result
             .map_err(|e| format!("Failed to write .npmrc: {}", e))?;
     }
 
-    emit_progress(&app, "config", "completed", None);
-
-    // Step 3: Set up service
-    emit_progress(&app, "service", "in_progress", Some("Configuring auto-start..."));
+    // Persist installer-level settings (e.g. the selected update channel)
+    let installer_settings = json!({
+        "channel": config.channel
+    });
+    let installer_settings_path = config_dir.join("installer.json");
+    fs::write(&installer_settings_path, serde_json::to_string_pretty(&installer_settings).unwrap())
+        .map_err(|e| format!("Failed to write installer.json: {}", e))?;
 
-    if config.enable_auto_start {
-        setup_service(&config_dir, &install_dir)?;
-    }
+    log::info!("Wrote configuration files to {}", config_dir.display());
+    emit_progress(app, "config", "completed", None);
 
-    emit_progress(&app, "service", "completed", None);
+    // Step 3: Set up service
+    emit_progress(app, "service", "in_progress", Some("Configuring auto-start..."));
+
+    let service_warning = if config.enable_auto_start {
+        match setup_service(app, config_dir, install_dir).await {
+            Ok(()) => {
+                log::info!("Auto-start service configured and activated");
+                emit_progress(app, "service", "completed", None);
+                None
+            }
+            Err(e) => {
+                // Activation failing (e.g. no user session to activate a
+                // systemd unit against) doesn't invalidate the rest of the
+                // installation, so report it rather than failing the install
+                let message = format!("Auto-start could not be activated automatically: {}", e);
+                log::warn!("{}", message);
+                emit_progress(app, "service", "error", Some(&message));
+                Some(message)
+            }
+        }
+    } else {
+        log::info!("Auto-start not requested, skipping service setup");
+        emit_progress(app, "service", "completed", None);
+        None
+    };
 
     // Step 4: Verify installation
-    emit_progress(&app, "verify", "in_progress", Some("Verifying installation..."));
+    emit_progress(app, "verify", "in_progress", Some("Verifying installation..."));
 
     // Check that configuration files exist
     if !settings_path.exists() {
-        return Err("Installation verification failed: settings.json not found".to_string());
+        let message = "Installation verification failed: settings.json not found".to_string();
+        log::error!("{}", message);
+        return Err(message);
     }
 
-    emit_progress(&app, "verify", "completed", None);
+    verify_bundle_integrity(&resource_path, install_dir)?;
+
+    log::info!("Installation verified successfully");
+    emit_progress(app, "verify", "completed", None);
+
+    Ok(service_warning)
+}
+
+/// Hash a file incrementally, in fixed-size chunks, so large binaries like
+/// the bundled Node.js runtime are never loaded into memory whole
+fn sha256_file(path: &PathBuf) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {} for hashing: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {} for hashing: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Verify the copied Node.js binary and signalk-server directory against the
+/// digest manifest shipped alongside the bundled resources. This detects
+/// corruption or an incomplete copy, not tampering — see `IntegrityManifest`.
+///
+/// This is a fail-closed check: the packaging step is required to ship a
+/// `manifest.json` alongside the bundled resources, and a missing manifest
+/// fails installation rather than silently skipping verification, since
+/// silently skipping would let a broken build (or a tampered bundle with the
+/// manifest stripped out) install unverified
+fn verify_bundle_integrity(resource_path: &PathBuf, install_dir: &PathBuf) -> Result<(), String> {
+    let manifest_path = resource_path.join("manifest.json");
+    let manifest_content = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read integrity manifest: {}", e))?;
+    let manifest: IntegrityManifest = serde_json::from_str(&manifest_content)
+        .map_err(|e| format!("Failed to parse integrity manifest: {}", e))?;
+
+    log::warn!("Integrity check is digest-only (manifest is unsigned); it detects corruption, not tampering");
+
+    for (relative_path, expected_digest) in &manifest.files {
+        let installed_path = install_dir.join(relative_path);
+
+        if !installed_path.exists() {
+            let message = format!("Integrity check failed: {} is missing from the installation", relative_path);
+            log::error!("{}", message);
+            return Err(message);
+        }
+
+        let actual_digest = sha256_file(&installed_path)?;
+
+        if &actual_digest != expected_digest {
+            let message = format!("Integrity check failed: {} does not match the expected digest", relative_path);
+            log::error!("{}", message);
+            return Err(message);
+        }
+    }
+
+    log::info!("Verified SHA256 digests for {} bundled files", manifest.files.len());
+
+    Ok(())
+}
+
+/// Run a command via the shell plugin and report failure (including stderr)
+/// through the "service" progress step
+async fn run_activation_command(
+    app: &AppHandle,
+    program: &str,
+    args: &[&str],
+) -> Result<(), String> {
+    use tauri_plugin_shell::ShellExt;
+
+    log::info!("Running {} {}", program, args.join(" "));
+
+    let output = app
+        .shell()
+        .command(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = format!("{} {} failed: {}", program, args.join(" "), stderr.trim());
+        log::error!("{}", message);
+        emit_progress(app, "service", "error", Some(&message));
+        return Err(message);
+    }
 
     Ok(())
 }
 
-/// Set up the system service for auto-start
-fn setup_service(config_dir: &PathBuf, install_dir: &PathBuf) -> Result<(), String> {
+/// Set up the system service for auto-start and activate it so the server
+/// starts without the user ever opening a terminal
+async fn setup_service(app: &AppHandle, config_dir: &PathBuf, install_dir: &PathBuf) -> Result<(), String> {
     let config_path = config_dir.to_string_lossy().to_string();
     let node_path = install_dir.join("node").to_string_lossy().to_string();
     let server_path = install_dir.join("signalk-server").join("bin").join("signalk-server").to_string_lossy().to_string();
@@ -287,8 +637,8 @@ fn setup_service(config_dir: &PathBuf, install_dir: &PathBuf) -> Result<(), Stri
         fs::write(&service_path, service_content)
             .map_err(|e| format!("Failed to write systemd service: {}", e))?;
 
-        // Enable the service (user would need to run systemctl --user daemon-reload && systemctl --user enable signalk)
-        // We'll document this or run it via a shell command
+        run_activation_command(app, "systemctl", &["--user", "daemon-reload"]).await?;
+        run_activation_command(app, "systemctl", &["--user", "enable", "--now", "signalk"]).await?;
     }
 
     #[cfg(target_os = "macos")]
@@ -307,6 +657,8 @@ fn setup_service(config_dir: &PathBuf, install_dir: &PathBuf) -> Result<(), Stri
         let plist_path = launch_agents_dir.join("org.signalk.server.plist");
         fs::write(&plist_path, plist_content)
             .map_err(|e| format!("Failed to write launchd plist: {}", e))?;
+
+        run_activation_command(app, "launchctl", &["load", "-w", &plist_path.to_string_lossy()]).await?;
     }
 
     #[cfg(target_os = "windows")]
@@ -322,7 +674,12 @@ fn setup_service(config_dir: &PathBuf, install_dir: &PathBuf) -> Result<(), Stri
         fs::write(&task_path, task_content)
             .map_err(|e| format!("Failed to write task XML: {}", e))?;
 
-        // Task would be registered via: schtasks /create /xml task_path /tn "SignalK Server"
+        run_activation_command(
+            app,
+            "schtasks",
+            &["/create", "/xml", &task_path.to_string_lossy(), "/tn", "SignalK Server"],
+        )
+        .await?;
     }
 
     Ok(())
@@ -0,0 +1,120 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Installation/runtime log for post-mortem diagnostics, rotated once it
+/// grows past this size so a stuck install can't fill up the disk
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+const LOG_FILE_NAME: &str = "install.log";
+const ROTATED_FILE_NAME: &str = "install.log.1";
+
+struct FileLogger {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileLogger {
+    /// Move the current log out of the way once it grows too large, keeping
+    /// a single previous generation (`install.log.1`) alongside the active
+    /// one, and reopen the handle so writes keep landing on `install.log`
+    /// rather than the renamed inode
+    fn rotate_if_needed(&self) {
+        let Ok(metadata) = fs::metadata(&self.path) else {
+            return;
+        };
+
+        if metadata.len() < MAX_LOG_BYTES {
+            return;
+        }
+
+        let rotated_path = self.path.with_file_name(ROTATED_FILE_NAME);
+        if fs::rename(&self.path, &rotated_path).is_err() {
+            return;
+        }
+
+        let Ok(new_file) = OpenOptions::new().create(true).append(true).open(&self.path) else {
+            return;
+        };
+
+        if let Ok(mut file) = self.file.lock() {
+            *file = new_file;
+        }
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        self.rotate_if_needed();
+
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(
+                file,
+                "{} [{}] {}: {}",
+                chrono::Local::now().to_rfc3339(),
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Install a file-backed logger that writes `install.log` into the config
+/// directory, so failures that never reach the UI can still be diagnosed
+pub fn init(config_dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(config_dir)
+        .map_err(|e| format!("Failed to create config directory for logging: {}", e))?;
+
+    let path = config_dir.join(LOG_FILE_NAME);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open install log: {}", e))?;
+
+    let logger = FileLogger {
+        path,
+        file: Mutex::new(file),
+    };
+
+    log::set_boxed_logger(Box::new(logger))
+        .map_err(|e| format!("Failed to initialize logger: {}", e))?;
+    log::set_max_level(LevelFilter::Info);
+
+    Ok(())
+}
+
+/// Read the install log (and the previous rotated generation, if any) so it
+/// can be attached to a bug report from the UI
+pub fn read_install_log(config_dir: &Path) -> Result<String, String> {
+    let mut contents = String::new();
+
+    let rotated_path = config_dir.join(ROTATED_FILE_NAME);
+    if let Ok(rotated) = fs::read_to_string(&rotated_path) {
+        contents.push_str(&rotated);
+    }
+
+    let path = config_dir.join(LOG_FILE_NAME);
+    contents.push_str(
+        &fs::read_to_string(&path).map_err(|e| format!("Failed to read install log: {}", e))?,
+    );
+
+    Ok(contents)
+}
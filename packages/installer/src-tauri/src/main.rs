@@ -2,6 +2,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod installer;
+mod logging;
 mod platform;
 mod serial;
 mod updater;
@@ -28,6 +29,7 @@ pub struct InstallerConfig {
     enable_auto_start: bool,
     #[serde(rename = "serialPorts")]
     serial_ports: Vec<String>,
+    channel: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -54,6 +56,18 @@ pub struct PlatformInfo {
     requires_admin: bool,
 }
 
+#[derive(Debug, Serialize)]
+pub struct InstallBackup {
+    #[serde(rename = "configBackupPath")]
+    config_backup_path: Option<String>,
+    #[serde(rename = "installBackupPath")]
+    install_backup_path: Option<String>,
+    /// Set when auto-start was requested but the service manager failed to
+    /// activate it; the installation itself still succeeded
+    #[serde(rename = "serviceWarning")]
+    service_warning: Option<String>,
+}
+
 #[tauri::command]
 fn check_existing_install() -> ExistingInstall {
     installer::check_existing_install()
@@ -70,10 +84,20 @@ fn get_platform_info() -> PlatformInfo {
 }
 
 #[tauri::command]
-async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result<(), String> {
+async fn run_installation(app: AppHandle, config: InstallerConfig) -> Result<InstallBackup, String> {
     installer::run_installation(app, config).await
 }
 
+#[tauri::command]
+fn restore_backup(config_backup_path: Option<String>, install_backup_path: Option<String>) -> Result<(), String> {
+    installer::restore_backup(config_backup_path, install_backup_path)
+}
+
+#[tauri::command]
+fn get_install_log() -> Result<String, String> {
+    logging::read_install_log(&installer::get_config_dir())
+}
+
 #[tauri::command]
 fn open_admin_ui() -> Result<(), String> {
     // Open default browser to SignalK admin UI
@@ -96,6 +120,11 @@ async fn install_update(app: AppHandle) -> Result<(), String> {
 }
 
 fn main() {
+    if let Err(e) = logging::init(&installer::get_config_dir()) {
+        eprintln!("Failed to initialize install log: {}", e);
+    }
+    log::info!("SignalK installer starting (v{})", env!("CARGO_PKG_VERSION"));
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -104,6 +133,8 @@ fn main() {
             list_serial_ports,
             get_platform_info,
             run_installation,
+            restore_backup,
+            get_install_log,
             open_admin_ui,
             close_installer,
             check_for_updates,
@@ -3,6 +3,8 @@ use crate::PlatformInfo;
 pub fn get_info() -> PlatformInfo {
     #[cfg(target_os = "linux")]
     {
+        log::info!("Detected platform: linux (systemd user service)");
+
         PlatformInfo {
             os: "linux".to_string(),
             service_manager: "systemd (user)".to_string(),
@@ -12,6 +14,8 @@ pub fn get_info() -> PlatformInfo {
 
     #[cfg(target_os = "macos")]
     {
+        log::info!("Detected platform: macos (launchd user agent)");
+
         PlatformInfo {
             os: "macos".to_string(),
             service_manager: "launchd (user agent)".to_string(),
@@ -21,6 +25,8 @@ pub fn get_info() -> PlatformInfo {
 
     #[cfg(target_os = "windows")]
     {
+        log::info!("Detected platform: windows (Task Scheduler)");
+
         PlatformInfo {
             os: "windows".to_string(),
             service_manager: "Task Scheduler".to_string(),
@@ -30,6 +36,8 @@ pub fn get_info() -> PlatformInfo {
 
     #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
     {
+        log::warn!("Unrecognized platform; auto-start will not be available");
+
         PlatformInfo {
             os: "unknown".to_string(),
             service_manager: "none".to_string(),
@@ -1,5 +1,7 @@
+use crate::installer;
 use serde::Serialize;
-use tauri::AppHandle;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Url};
 use tauri_plugin_updater::UpdaterExt;
 
 #[derive(Debug, Serialize)]
@@ -16,13 +18,63 @@ pub struct UpdateInfo {
     pub download_url: Option<String>,
 }
 
+/// Build the release feed endpoint for the given update channel
+fn channel_endpoint(channel: &str) -> Result<Url, String> {
+    Url::parse(&format!("https://updates.signalk.org/installer/{channel}/latest.json"))
+        .map_err(|e| format!("Failed to build update endpoint for channel '{}': {}", channel, e))
+}
+
+/// Returns the update if it is available on the caller's selected channel,
+/// dropping mismatches so beta testers never block a stable user (or vice versa)
+async fn check_update_on_channel(
+    app: &AppHandle,
+    channel: &str,
+) -> Result<Option<tauri_plugin_updater::Update>, String> {
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![channel_endpoint(channel)?])
+        .map_err(|e| format!("Failed to configure updater: {}", e))?
+        .build()
+        .map_err(|e| format!("Failed to get updater: {}", e))?;
+
+    match updater.check().await {
+        Ok(Some(update)) => {
+            let remote_channel = update
+                .raw_json
+                .get("channel")
+                .and_then(|v| v.as_str())
+                .unwrap_or("stable");
+
+            if remote_channel == channel {
+                log::info!("Update {} available on channel '{}'", update.version, channel);
+                Ok(Some(update))
+            } else {
+                log::info!(
+                    "Ignoring update {} on channel '{}' (selected channel is '{}')",
+                    update.version,
+                    remote_channel,
+                    channel
+                );
+                Ok(None)
+            }
+        }
+        Ok(None) => {
+            log::info!("No update available on channel '{}'", channel);
+            Ok(None)
+        }
+        Err(e) => {
+            log::warn!("Update check on channel '{}' failed: {}", channel, e);
+            Err(e.to_string())
+        }
+    }
+}
+
 /// Check if a new version of SignalK Installer is available
 pub async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, String> {
     let current_version = env!("CARGO_PKG_VERSION").to_string();
+    let channel = installer::read_channel();
 
-    let updater = app.updater().map_err(|e| format!("Failed to get updater: {}", e))?;
-
-    match updater.check().await {
+    match check_update_on_channel(&app, &channel).await {
         Ok(Some(update)) => {
             Ok(UpdateInfo {
                 update_available: true,
@@ -56,32 +108,57 @@ pub async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, String> {
 
 /// Download and install an available update
 pub async fn install_update(app: AppHandle) -> Result<(), String> {
-    let updater = app.updater().map_err(|e| format!("Failed to get updater: {}", e))?;
+    let channel = installer::read_channel();
 
-    match updater.check().await {
+    match check_update_on_channel(&app, &channel).await {
         Ok(Some(update)) => {
             // Download the update
-            let mut downloaded = 0;
-            let mut total = 0;
+            let mut downloaded: usize = 0;
+            let mut total: u64 = 0;
+
+            let progress_app = app.clone();
+            let restart_app = app.clone();
 
             update
                 .download_and_install(
-                    |chunk_length, content_length| {
+                    move |chunk_length, content_length| {
                         downloaded += chunk_length;
-                        total = content_length.unwrap_or(0);
-                        // Progress could be emitted here if needed
-                        let _ = (downloaded, total);
+                        if let Some(content_length) = content_length {
+                            total = content_length;
+                        }
+
+                        let percentage = if total > 0 {
+                            (downloaded as f64 / total as f64) * 100.0
+                        } else {
+                            0.0
+                        };
+
+                        let _ = progress_app.emit(
+                            "update-progress",
+                            json!({
+                                "downloaded": downloaded,
+                                "total": total,
+                                "percentage": percentage
+                            }),
+                        );
                     },
-                    || {
+                    move || {
                         // Called before install - app will restart
+                        let _ = restart_app.emit("update-complete", json!({}));
                     },
                 )
                 .await
-                .map_err(|e| format!("Failed to download and install update: {}", e))?;
+                .map_err(|e| {
+                    let message = format!("Failed to download and install update: {}", e);
+                    log::error!("{}", message);
+                    message
+                })?;
+
+            log::info!("Update downloaded and installed");
 
             Ok(())
         }
-        Ok(None) => Err("No update available".to_string()),
+        Ok(None) => Err("No update available on the selected channel".to_string()),
         Err(e) => Err(format!("Failed to check for updates: {}", e)),
     }
 }